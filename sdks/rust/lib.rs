@@ -1,5 +1,17 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use rand::Rng;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+use tracing::debug;
+use url::Url;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum ProcessorType {
@@ -37,36 +49,354 @@ pub struct OutputSchema {
     pub retries_attempted: u32,
 }
 
+/// Validation and processor metadata for a streamed [`Client::process_request_to_writer`]
+/// call, returned separately from the (potentially large) result payload.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StreamedMetadata {
+    pub validation: ValidationResult,
+    pub processor_used: ProcessorType,
+    pub processing_time_ms: f64,
+}
+
+/// Client-side retry behavior for transient failures. The default performs no
+/// retries, preserving the historical single-attempt behavior of
+/// [`Client::process_request`].
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            base_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Whether an HTTP status is a transient failure worth retrying: 429 (rate
+/// limited) or 502/503/504 (upstream/gateway unavailable). Any other status,
+/// including other 4xx client errors, is not retried.
+fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 429 | 502 | 503 | 504)
+}
+
+/// Full-jitter exponential backoff: a uniform random duration in
+/// `[0, base_backoff * 2^attempt]`.
+fn jittered_backoff(base_backoff: Duration, attempt: u32) -> Duration {
+    let max_delay = base_backoff * 2u32.saturating_pow(attempt);
+    let max_nanos = u64::try_from(max_delay.as_nanos()).unwrap_or(u64::MAX);
+    let jitter_nanos = rand::thread_rng().gen_range(0..=max_nanos);
+    Duration::from_nanos(jitter_nanos)
+}
+
+/// Serializable summary of a [`reqwest::StatusCode`], since the status code
+/// type itself isn't `Serialize`. Used to log response status as structured
+/// fields.
+#[derive(Debug, Serialize)]
+pub struct LoggedStatus {
+    pub code: u16,
+    pub message: Option<&'static str>,
+}
+
+impl From<reqwest::StatusCode> for LoggedStatus {
+    fn from(status: reqwest::StatusCode) -> Self {
+        Self {
+            code: status.as_u16(),
+            message: status.canonical_reason(),
+        }
+    }
+}
+
+/// Serializable summary of a [`HeaderMap`], since header maps aren't
+/// `Serialize`. Header values that aren't valid UTF-8 are rendered lossily.
+#[derive(Debug, Serialize)]
+pub struct LoggedHeaders(pub BTreeMap<String, String>);
+
+impl From<&HeaderMap> for LoggedHeaders {
+    fn from(headers: &HeaderMap) -> Self {
+        let map = headers
+            .iter()
+            .map(|(name, value)| {
+                (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned())
+            })
+            .collect();
+        Self(map)
+    }
+}
+
+/// Which credential a request should be authenticated with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthScope {
+    /// The regular user/API token, sent as `X-API-Key`.
+    User,
+    /// The privileged admin token, sent as `X-Admin-Key`. Requires the
+    /// client to have been configured with an admin token.
+    Admin,
+}
+
+/// Parses `base_url` into a [`Url`] and normalizes it to end with `/`, so
+/// [`Url::join`] composes a request path correctly regardless of whether
+/// `base_url` itself has a trailing slash.
+fn parse_base_url(base_url: &str) -> Result<Url, Box<dyn std::error::Error>> {
+    let mut url = Url::parse(base_url).map_err(|e| format!("Invalid base URL: {}", e))?;
+    if !url.path().ends_with('/') {
+        let path = url.path().to_string();
+        url.set_path(&format!("{}/", path));
+    }
+    Ok(url)
+}
+
 pub struct Client {
-    base_url: String,
+    base_url: Url,
     api_key: Option<String>,
+    admin_token: Option<String>,
     http_client: reqwest::Client,
+    retry_policy: RetryPolicy,
 }
 
 impl Client {
-    pub fn new(base_url: String, api_key: Option<String>) -> Self {
-        Self {
-            base_url,
+    pub fn new(base_url: String, api_key: Option<String>) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            base_url: parse_base_url(&base_url)?,
             api_key,
+            admin_token: None,
             http_client: reqwest::Client::new(),
+            retry_policy: RetryPolicy::default(),
+        })
+    }
+
+    /// Creates a client authenticated with a user/API token only.
+    pub fn with_api_token(base_url: String, api_token: String) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::new(base_url, Some(api_token))
+    }
+
+    /// Creates a client authenticated with an admin token only.
+    pub fn with_admin_token(base_url: String, admin_token: String) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            base_url: parse_base_url(&base_url)?,
+            api_key: None,
+            admin_token: Some(admin_token),
+            http_client: reqwest::Client::new(),
+            retry_policy: RetryPolicy::default(),
+        })
+    }
+
+    /// Creates an unauthenticated client. Requests requiring [`AuthScope::Admin`]
+    /// will error since no admin token is configured.
+    pub fn with_no_tokens(base_url: String) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::new(base_url, None)
+    }
+
+    pub fn builder(base_url: String) -> ClientBuilder {
+        ClientBuilder::new(base_url)
+    }
+
+    /// Builds the auth/content headers for `scope`, erroring if `scope` is
+    /// [`AuthScope::Admin`] but no admin token is configured.
+    fn build_headers(&self, scope: AuthScope) -> Result<HeaderMap, Box<dyn std::error::Error>> {
+        let mut headers = HeaderMap::new();
+        match scope {
+            AuthScope::User => {
+                if let Some(ref key) = self.api_key {
+                    let mut header_value = HeaderValue::from_str(key)
+                        .map_err(|e| format!("Invalid API key header: {}", e))?;
+                    header_value.set_sensitive(true);
+                    headers.insert("X-API-Key", header_value);
+                }
+            }
+            AuthScope::Admin => {
+                let key = self.admin_token.as_ref().ok_or(
+                    "Admin-scoped request requires a client configured with an admin token",
+                )?;
+                let mut header_value = HeaderValue::from_str(key)
+                    .map_err(|e| format!("Invalid admin token header: {}", e))?;
+                header_value.set_sensitive(true);
+                headers.insert("X-Admin-Key", header_value);
+            }
         }
+        Ok(headers)
+    }
+
+    /// Resolves `path` against the already-parsed `base_url`, composing
+    /// correctly regardless of whether `path` has a leading slash.
+    fn build_url(&self, path: &str) -> Result<Url, Box<dyn std::error::Error>> {
+        Ok(self.base_url.join(path.trim_start_matches('/'))?)
+    }
+
+    async fn parse_response<T: DeserializeOwned>(&self, response: reqwest::Response) -> Result<T, Box<dyn std::error::Error>> {
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("Request failed with status {}: {}", status, text).into());
+        }
+        Ok(response.json::<T>().await?)
+    }
+
+    /// Issues a `GET` to `path`, resolved against `base_url`, authenticated
+    /// with `scope`.
+    pub async fn get<T: DeserializeOwned>(&self, path: &str, scope: AuthScope) -> Result<T, Box<dyn std::error::Error>> {
+        let url = self.build_url(path)?;
+        let headers = self.build_headers(scope)?;
+        let response = self.http_client.get(url).headers(headers).send().await?;
+        self.parse_response(response).await
+    }
+
+    /// Issues a `POST` of `body` to `path`, resolved against `base_url`,
+    /// authenticated with `scope`.
+    pub async fn post<B: Serialize, T: DeserializeOwned>(&self, path: &str, body: &B, scope: AuthScope) -> Result<T, Box<dyn std::error::Error>> {
+        let url = self.build_url(path)?;
+        let headers = self.build_headers(scope)?;
+        let response = self.http_client.post(url).headers(headers).json(body).send().await?;
+        self.parse_response(response).await
     }
 
     pub async fn process_request(&self, request: &ProcessingRequest) -> Result<OutputSchema, Box<dyn std::error::Error>> {
-        let mut headers = HeaderMap::new();
-        if let Some(ref key) = self.api_key {
-            let header_value = HeaderValue::from_str(key)
-                .map_err(|e| format!("Invalid API key header: {}", e))?;
-            headers.insert("X-API-Key", header_value);
+        let headers = self.build_headers(AuthScope::User)?;
+        let url = self.build_url("process/request")?;
+        let deadline = request
+            .timeout_seconds
+            .map(|secs| Instant::now() + Duration::from_secs_f64(secs.max(0.0)));
+
+        debug!(
+            target_url = %url,
+            processor_type = ?request.processor_type,
+            input_tokens = request.input_tokens,
+            "sending process_request"
+        );
+
+        let mut retries_attempted = 0u32;
+        loop {
+            let mut attempt = self.http_client
+                .post(url.clone())
+                .headers(headers.clone())
+                .json(request);
+
+            if let Some(deadline) = deadline {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Err("Request timed out".into());
+                }
+                attempt = attempt.timeout(remaining);
+            }
+
+            let sent = attempt.send().await;
+
+            let response = match sent {
+                Ok(response) => response,
+                Err(err) => {
+                    if retries_attempted >= self.retry_policy.max_retries {
+                        return Err(err.into());
+                    }
+                    self.wait_before_retry(retries_attempted, None, deadline).await?;
+                    retries_attempted += 1;
+                    continue;
+                }
+            };
+
+            let status = response.status();
+            debug!(
+                status = ?LoggedStatus::from(status),
+                headers = ?LoggedHeaders::from(response.headers()),
+                "received process_request response"
+            );
+
+            if status.is_success() {
+                let mut output = response.json::<OutputSchema>().await?;
+                output.retries_attempted = retries_attempted;
+                return Ok(output);
+            }
+
+            if !is_retryable_status(status.as_u16()) || retries_attempted >= self.retry_policy.max_retries {
+                let text = response.text().await.unwrap_or_default();
+                return Err(format!("Request failed with status {}: {}", status, text).into());
+            }
+
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<u64>().ok())
+                .map(Duration::from_secs);
+
+            self.wait_before_retry(retries_attempted, retry_after, deadline).await?;
+            retries_attempted += 1;
         }
+    }
+
+    /// Sleeps before the next retry attempt, preferring `retry_after` when the
+    /// server supplied one and otherwise using full-jitter exponential
+    /// backoff. Returns an error if the wait would exceed the request's
+    /// `timeout_seconds` deadline.
+    async fn wait_before_retry(
+        &self,
+        attempt: u32,
+        retry_after: Option<Duration>,
+        deadline: Option<Instant>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let backoff = retry_after.unwrap_or_else(|| jittered_backoff(self.retry_policy.base_backoff, attempt));
+
+        if let Some(deadline) = deadline {
+            if Instant::now() + backoff >= deadline {
+                return Err("Request timed out while waiting to retry".into());
+            }
+        }
+
+        tokio::time::sleep(backoff).await;
+        Ok(())
+    }
+
+    /// Convenience wrapper around [`Client::process_request`] that also
+    /// persists `result` to `path` via [`OutputSchema::save_result_to`].
+    pub async fn process_and_save(
+        &self,
+        request: &ProcessingRequest,
+        path: impl AsRef<Path>,
+    ) -> Result<OutputSchema, Box<dyn std::error::Error>> {
+        let output = self.process_request(request).await?;
+        output.save_result_to(path)?;
+        Ok(output)
+    }
+
+    /// Like [`Client::process_request`], but incrementally scans the response
+    /// body and forwards the `result` field's bytes to `writer` in ~4 KiB
+    /// flushes as they're parsed, instead of materializing the whole response
+    /// (or a second copy of `result`) in memory. This does not call
+    /// [`Client::process_request`] — `/process/request` returns a single JSON
+    /// document shaped like [`OutputSchema`], so getting a genuine memory
+    /// bound requires reading that document off the wire one token at a time
+    /// rather than parsing it whole first. The much smaller surrounding
+    /// fields are buffered normally and returned as [`StreamedMetadata`].
+    ///
+    /// This method does not retry; callers needing retry behavior for large
+    /// streamed results should call it from within their own retry loop.
+    pub async fn process_request_to_writer(
+        &self,
+        request: &ProcessingRequest,
+        mut writer: impl Write,
+    ) -> Result<StreamedMetadata, Box<dyn std::error::Error>> {
+        let headers = self.build_headers(AuthScope::User)?;
+        let url = self.build_url("process/request")?;
+        let deadline = request
+            .timeout_seconds
+            .map(|secs| Instant::now() + Duration::from_secs_f64(secs.max(0.0)));
 
-        let url = format!("{}/process/request", self.base_url);
-        let response = self.http_client
+        let mut attempt = self.http_client
             .post(url)
             .headers(headers)
-            .json(request)
-            .send()
-            .await?;
+            .json(request);
+
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err("Request timed out".into());
+            }
+            attempt = attempt.timeout(remaining);
+        }
+
+        let response = attempt.send().await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -74,7 +404,775 @@ impl Client {
             return Err(format!("Request failed with status {}: {}", status, text).into());
         }
 
-        let output = response.json::<OutputSchema>().await?;
-        Ok(output)
+        let mut body = ChunkedJsonBody::new(response.bytes_stream());
+        let mut other_fields: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+        let mut saw_result = false;
+
+        body.skip_whitespace().await?;
+        body.expect_byte(b'{').await?;
+        body.skip_whitespace().await?;
+
+        if body.peek_byte().await? == Some(b'}') {
+            body.next_byte().await?;
+        } else {
+            loop {
+                let key = decode_json_string(&mut body).await?;
+                body.skip_whitespace().await?;
+                body.expect_byte(b':').await?;
+                body.skip_whitespace().await?;
+
+                if key == "result" {
+                    stream_result_value(&mut body, &mut writer).await?;
+                    saw_result = true;
+                } else {
+                    other_fields.insert(key, capture_raw_value(&mut body).await?);
+                }
+
+                body.skip_whitespace().await?;
+                match body.next_byte().await? {
+                    Some(b',') => {
+                        body.skip_whitespace().await?;
+                        continue;
+                    }
+                    Some(b'}') => break,
+                    Some(byte) => return Err(format!("Unexpected byte '{}' while parsing response body", byte as char).into()),
+                    None => return Err("Unexpected end of response body while parsing response body".into()),
+                }
+            }
+        }
+
+        if !saw_result {
+            return Err("Response JSON did not contain a `result` field".into());
+        }
+
+        Ok(serde_json::from_slice(&build_metadata_json(&other_fields)?)?)
+    }
+}
+
+/// Pull-based byte cursor over a chunked HTTP response body, used by
+/// [`Client::process_request_to_writer`] to scan the response JSON without
+/// buffering it whole.
+struct ChunkedJsonBody {
+    stream: std::pin::Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>,
+    chunk: Bytes,
+    pos: usize,
+}
+
+impl ChunkedJsonBody {
+    fn new(stream: impl Stream<Item = reqwest::Result<Bytes>> + Send + 'static) -> Self {
+        Self {
+            stream: Box::pin(stream),
+            chunk: Bytes::new(),
+            pos: 0,
+        }
+    }
+
+    /// Ensures at least one more byte is available, pulling further network
+    /// chunks as needed. Returns `false` at end of stream.
+    async fn fill(&mut self) -> Result<bool, Box<dyn std::error::Error>> {
+        while self.pos >= self.chunk.len() {
+            match self.stream.next().await {
+                Some(chunk) => {
+                    self.chunk = chunk?;
+                    self.pos = 0;
+                }
+                None => return Ok(false),
+            }
+        }
+        Ok(true)
+    }
+
+    async fn next_byte(&mut self) -> Result<Option<u8>, Box<dyn std::error::Error>> {
+        if !self.fill().await? {
+            return Ok(None);
+        }
+        let byte = self.chunk[self.pos];
+        self.pos += 1;
+        Ok(Some(byte))
+    }
+
+    async fn peek_byte(&mut self) -> Result<Option<u8>, Box<dyn std::error::Error>> {
+        if !self.fill().await? {
+            return Ok(None);
+        }
+        Ok(Some(self.chunk[self.pos]))
+    }
+
+    async fn skip_whitespace(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        while let Some(byte) = self.peek_byte().await? {
+            if !byte.is_ascii_whitespace() {
+                break;
+            }
+            self.next_byte().await?;
+        }
+        Ok(())
+    }
+
+    async fn expect_byte(&mut self, expected: u8) -> Result<(), Box<dyn std::error::Error>> {
+        match self.next_byte().await? {
+            Some(byte) if byte == expected => Ok(()),
+            Some(byte) => Err(format!("expected '{}' but found '{}'", expected as char, byte as char).into()),
+            None => Err(format!("expected '{}' but reached end of response body", expected as char).into()),
+        }
+    }
+}
+
+/// Reads a 4-hex-digit `\uXXXX` escape (the `\u` itself already consumed) and
+/// returns the UTF-16 code unit.
+async fn read_unicode_escape(body: &mut ChunkedJsonBody) -> Result<u16, Box<dyn std::error::Error>> {
+    let mut hex = [0u8; 4];
+    for slot in hex.iter_mut() {
+        *slot = body.next_byte().await?.ok_or("unterminated unicode escape")?;
+    }
+    let hex = std::str::from_utf8(&hex).map_err(|e| format!("invalid unicode escape: {}", e))?;
+    Ok(u16::from_str_radix(hex, 16).map_err(|e| format!("invalid unicode escape: {}", e))?)
+}
+
+/// Decodes a `\uXXXX` (or surrogate-pair `\uXXXX\uXXXX`) escape into UTF-8
+/// bytes appended to `out`.
+async fn decode_unicode_escape(body: &mut ChunkedJsonBody, first: u16, out: &mut Vec<u8>) -> Result<(), Box<dyn std::error::Error>> {
+    let code_point = if (0xD800..=0xDBFF).contains(&first) {
+        body.expect_byte(b'\\').await?;
+        body.expect_byte(b'u').await?;
+        let second = read_unicode_escape(body).await?;
+        if !(0xDC00..=0xDFFF).contains(&second) {
+            return Err("invalid low surrogate in unicode escape".into());
+        }
+        0x10000 + (((first as u32 - 0xD800) << 10) | (second as u32 - 0xDC00))
+    } else {
+        first as u32
+    };
+    let ch = char::from_u32(code_point).ok_or("invalid unicode escape")?;
+    let mut buf = [0u8; 4];
+    out.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+    Ok(())
+}
+
+/// Decodes the next character of a JSON string into `out`: a literal byte,
+/// or an escape sequence (`\n`, `\uXXXX`, ...). Returns `true` once the
+/// closing (unescaped) quote has been consumed.
+async fn decode_string_unit(body: &mut ChunkedJsonBody, out: &mut Vec<u8>) -> Result<bool, Box<dyn std::error::Error>> {
+    match body.next_byte().await?.ok_or("unterminated string")? {
+        b'"' => return Ok(true),
+        b'\\' => match body.next_byte().await?.ok_or("unterminated escape sequence")? {
+            b'"' => out.push(b'"'),
+            b'\\' => out.push(b'\\'),
+            b'/' => out.push(b'/'),
+            b'b' => out.push(0x08),
+            b'f' => out.push(0x0C),
+            b'n' => out.push(b'\n'),
+            b'r' => out.push(b'\r'),
+            b't' => out.push(b'\t'),
+            b'u' => {
+                let code = read_unicode_escape(body).await?;
+                decode_unicode_escape(body, code, out).await?;
+            }
+            other => return Err(format!("invalid escape sequence '\\{}'", other as char).into()),
+        },
+        other => out.push(other),
+    }
+    Ok(false)
+}
+
+/// Decodes a full JSON string value (leading quote not yet consumed) into an
+/// owned `String`. Used for object keys and other small fields, which are
+/// cheap to buffer in full.
+async fn decode_json_string(body: &mut ChunkedJsonBody) -> Result<String, Box<dyn std::error::Error>> {
+    body.expect_byte(b'"').await?;
+    let mut out = Vec::new();
+    while !decode_string_unit(body, &mut out).await? {}
+    Ok(String::from_utf8(out)?)
+}
+
+/// Streams a JSON string value (leading quote not yet consumed) to `writer`,
+/// decoding escapes and flushing every ~4 KiB instead of buffering the whole
+/// decoded string.
+async fn stream_json_string(body: &mut ChunkedJsonBody, writer: &mut impl Write) -> Result<(), Box<dyn std::error::Error>> {
+    const FLUSH_SIZE: usize = 4096;
+    body.expect_byte(b'"').await?;
+    let mut out = Vec::with_capacity(FLUSH_SIZE);
+    loop {
+        if decode_string_unit(body, &mut out).await? {
+            break;
+        }
+        if out.len() >= FLUSH_SIZE {
+            writer.write_all(&out)?;
+            out.clear();
+        }
+    }
+    if !out.is_empty() {
+        writer.write_all(&out)?;
+    }
+    Ok(())
+}
+
+/// Streams a JSON object/array/number/bool/null value (raw bytes, no
+/// escape-decoding) to `writer`, flushing every ~4 KiB.
+async fn stream_raw_value(body: &mut ChunkedJsonBody, writer: &mut impl Write) -> Result<(), Box<dyn std::error::Error>> {
+    const FLUSH_SIZE: usize = 4096;
+    let mut out = Vec::with_capacity(FLUSH_SIZE);
+
+    match body.peek_byte().await?.ok_or("unexpected end of response body while reading result")? {
+        b'{' | b'[' => {
+            let mut depth = 0i32;
+            let mut in_string = false;
+            let mut escaped = false;
+            loop {
+                let byte = body.next_byte().await?.ok_or("unexpected end of response body while reading result")?;
+                out.push(byte);
+                if in_string {
+                    if escaped {
+                        escaped = false;
+                    } else if byte == b'\\' {
+                        escaped = true;
+                    } else if byte == b'"' {
+                        in_string = false;
+                    }
+                } else {
+                    match byte {
+                        b'"' => in_string = true,
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                if out.len() >= FLUSH_SIZE {
+                    writer.write_all(&out)?;
+                    out.clear();
+                }
+            }
+        }
+        _ => loop {
+            match body.peek_byte().await? {
+                Some(byte) if byte == b',' || byte == b'}' || byte == b']' || byte.is_ascii_whitespace() => break,
+                Some(_) => {
+                    out.push(body.next_byte().await?.unwrap());
+                    if out.len() >= FLUSH_SIZE {
+                        writer.write_all(&out)?;
+                        out.clear();
+                    }
+                }
+                None => break,
+            }
+        },
+    }
+
+    if !out.is_empty() {
+        writer.write_all(&out)?;
+    }
+    Ok(())
+}
+
+/// Streams the `result` field's value to `writer`: decoded if it's a JSON
+/// string, raw bytes otherwise.
+async fn stream_result_value(body: &mut ChunkedJsonBody, writer: &mut impl Write) -> Result<(), Box<dyn std::error::Error>> {
+    if body.peek_byte().await? == Some(b'"') {
+        stream_json_string(body, writer).await
+    } else {
+        stream_raw_value(body, writer).await
+    }
+}
+
+/// Captures a JSON value's exact raw bytes (quotes/escapes left undecoded)
+/// without interpreting it, for the small surrounding fields that get parsed
+/// normally once the full object has been scanned.
+async fn capture_raw_value(body: &mut ChunkedJsonBody) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut raw = Vec::new();
+
+    match body.peek_byte().await?.ok_or("unexpected end of response body while reading a field")? {
+        b'"' => {
+            raw.push(body.next_byte().await?.unwrap());
+            let mut escaped = false;
+            loop {
+                let byte = body.next_byte().await?.ok_or("unterminated string")?;
+                raw.push(byte);
+                if escaped {
+                    escaped = false;
+                } else if byte == b'\\' {
+                    escaped = true;
+                } else if byte == b'"' {
+                    break;
+                }
+            }
+        }
+        b'{' | b'[' => {
+            let mut depth = 0i32;
+            let mut in_string = false;
+            let mut escaped = false;
+            loop {
+                let byte = body.next_byte().await?.ok_or("unexpected end of response body while reading a field")?;
+                raw.push(byte);
+                if in_string {
+                    if escaped {
+                        escaped = false;
+                    } else if byte == b'\\' {
+                        escaped = true;
+                    } else if byte == b'"' {
+                        in_string = false;
+                    }
+                    continue;
+                }
+                match byte {
+                    b'"' => in_string = true,
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => loop {
+            match body.peek_byte().await? {
+                Some(byte) if byte == b',' || byte == b'}' || byte == b']' || byte.is_ascii_whitespace() => break,
+                Some(_) => raw.push(body.next_byte().await?.unwrap()),
+                None => break,
+            }
+        },
+    }
+
+    Ok(raw)
+}
+
+/// Reassembles the small fields captured by [`capture_raw_value`] into a JSON
+/// object matching [`StreamedMetadata`]'s shape, so it can be parsed with
+/// `serde_json` as usual.
+fn build_metadata_json(fields: &BTreeMap<String, Vec<u8>>) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut out = Vec::new();
+    out.push(b'{');
+    for (index, key) in ["validation", "processor_used", "processing_time_ms"].iter().enumerate() {
+        let raw = fields
+            .get(*key)
+            .ok_or_else(|| format!("Response JSON is missing the `{}` field", key))?;
+        if index > 0 {
+            out.push(b',');
+        }
+        out.push(b'"');
+        out.extend_from_slice(key.as_bytes());
+        out.extend_from_slice(b"\":");
+        out.extend_from_slice(raw);
+    }
+    out.push(b'}');
+    Ok(out)
+}
+
+impl OutputSchema {
+    /// Writes `result` to disk. A JSON string is written verbatim; any other
+    /// JSON shape is serialized as pretty-printed JSON. If `path` names a
+    /// directory rather than a file, the filename is derived from
+    /// `validation.input_hash` with an extension chosen from the JSON shape
+    /// (`.txt` for a string, `.json` otherwise).
+    pub fn save_result_to(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let target = if path.is_dir() {
+            let extension = if self.result.is_string() { "txt" } else { "json" };
+            path.join(format!("{}.{}", self.validation.input_hash, extension))
+        } else {
+            path.to_path_buf()
+        };
+
+        let contents = match self.result.as_str() {
+            Some(text) => text.to_string(),
+            None => serde_json::to_string_pretty(&self.result)?,
+        };
+
+        fs::write(target, contents)?;
+        Ok(())
+    }
+}
+
+/// Builds a [`Client`] with transport options beyond the defaults used by
+/// [`Client::new`]: an outbound proxy, a custom user-agent, relaxed TLS
+/// verification for self-hosted/dev endpoints, and a default request timeout.
+pub struct ClientBuilder {
+    base_url: String,
+    api_key: Option<String>,
+    admin_token: Option<String>,
+    proxy: Option<String>,
+    user_agent: Option<String>,
+    danger_accept_invalid_certs: bool,
+    timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+}
+
+impl ClientBuilder {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            base_url,
+            api_key: None,
+            admin_token: None,
+            proxy: None,
+            user_agent: None,
+            danger_accept_invalid_certs: false,
+            timeout: None,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn admin_token(mut self, admin_token: impl Into<String>) -> Self {
+        self.admin_token = Some(admin_token.into());
+        self
+    }
+
+    /// Route all requests through `proxy_url` (e.g. `http://proxy.corp:8080`).
+    pub fn proxy(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy = Some(proxy_url.into());
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Disable TLS certificate verification. Only intended for self-hosted
+    /// endpoints with self-signed certificates during development.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.danger_accept_invalid_certs = accept;
+        self
+    }
+
+    pub fn default_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry transport errors and 429/502/503/504 responses up to
+    /// `max_retries` times with exponential backoff.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.retry_policy.max_retries = max_retries;
+        self
+    }
+
+    pub fn base_backoff(mut self, base_backoff: Duration) -> Self {
+        self.retry_policy.base_backoff = base_backoff;
+        self
+    }
+
+    pub fn build(self) -> Result<Client, Box<dyn std::error::Error>> {
+        let mut builder = reqwest::ClientBuilder::new();
+
+        if let Some(ref user_agent) = self.user_agent {
+            let header_value = HeaderValue::from_str(user_agent)
+                .map_err(|e| format!("Invalid user-agent header: {}", e))?;
+            builder = builder.user_agent(header_value);
+        }
+
+        if let Some(ref proxy_url) = self.proxy {
+            let proxy = reqwest::Proxy::all(proxy_url)
+                .map_err(|e| format!("Invalid proxy URL: {}", e))?;
+            builder = builder.proxy(proxy);
+        }
+
+        if self.danger_accept_invalid_certs {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        let http_client = builder.build()?;
+
+        Ok(Client {
+            base_url: parse_base_url(&self.base_url)?,
+            api_key: self.api_key,
+            admin_token: self.admin_token,
+            http_client,
+            retry_policy: self.retry_policy,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retryable_statuses_match_transient_failures() {
+        for status in [429, 502, 503, 504] {
+            assert!(is_retryable_status(status), "{status} should be retryable");
+        }
+        for status in [400, 401, 403, 404, 409, 422, 500] {
+            assert!(!is_retryable_status(status), "{status} should not be retryable");
+        }
+    }
+
+    #[test]
+    fn jittered_backoff_stays_within_the_exponential_bound() {
+        let base = Duration::from_millis(100);
+        for attempt in 0..5 {
+            let max_delay = base * 2u32.saturating_pow(attempt);
+            for _ in 0..50 {
+                let backoff = jittered_backoff(base, attempt);
+                assert!(
+                    backoff <= max_delay,
+                    "attempt {attempt}: {backoff:?} exceeded bound {max_delay:?}"
+                );
+            }
+        }
+    }
+
+    fn sample_output(result: serde_json::Value) -> OutputSchema {
+        OutputSchema {
+            result,
+            validation: ValidationResult {
+                status: "ok".to_string(),
+                is_valid: true,
+                input_hash: "deadbeef".to_string(),
+                errors: Vec::new(),
+            },
+            processor_used: ProcessorType::Cloud,
+            processing_time_ms: 12.5,
+            retries_attempted: 0,
+        }
+    }
+
+    #[test]
+    fn save_result_to_file_writes_string_result_verbatim() {
+        let output = sample_output(serde_json::Value::String("hello world".to_string()));
+        let dir = std::env::temp_dir().join(format!("strict_test_file_string_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("out.txt");
+
+        output.save_result_to(&file_path).unwrap();
+
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "hello world");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn save_result_to_file_writes_pretty_json_for_non_string_result() {
+        let output = sample_output(serde_json::json!({"a": 1}));
+        let dir = std::env::temp_dir().join(format!("strict_test_file_json_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("out.json");
+
+        output.save_result_to(&file_path).unwrap();
+
+        let expected = serde_json::to_string_pretty(&output.result).unwrap();
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), expected);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn save_result_to_directory_derives_filename_and_extension_from_result_shape() {
+        let string_output = sample_output(serde_json::Value::String("hi".to_string()));
+        let json_output = sample_output(serde_json::json!({"a": 1}));
+        let dir = std::env::temp_dir().join(format!("strict_test_dir_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        string_output.save_result_to(&dir).unwrap();
+        json_output.save_result_to(&dir).unwrap();
+
+        assert!(dir.join(format!("{}.txt", string_output.validation.input_hash)).exists());
+        assert!(dir.join(format!("{}.json", json_output.validation.input_hash)).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn sample_request() -> ProcessingRequest {
+        ProcessingRequest {
+            input_data: "hello".to_string(),
+            input_tokens: 3,
+            processor_type: None,
+            timeout_seconds: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn process_request_to_writer_streams_a_string_result_and_decodes_escapes() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/process/request"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(
+                r#"{"result":"line one\nline two ☃","validation":{"status":"ok","is_valid":true,"input_hash":"deadbeef","errors":[]},"processor_used":"cloud","processing_time_ms":12.5}"#,
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+
+        let client = Client::with_no_tokens(server.uri()).unwrap();
+        let mut out = Vec::new();
+        let metadata = client.process_request_to_writer(&sample_request(), &mut out).await.unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "line one\nline two \u{2603}");
+        assert_eq!(metadata.validation.input_hash, "deadbeef");
+        assert_eq!(metadata.processing_time_ms, 12.5);
+    }
+
+    #[tokio::test]
+    async fn process_request_to_writer_streams_a_non_string_result_verbatim() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/process/request"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(
+                r#"{"validation":{"status":"ok","is_valid":true,"input_hash":"abc123","errors":[]},"result":{"a":[1,2,3],"b":"nested \"quote\""},"processor_used":"local","processing_time_ms":1.0}"#,
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+
+        let client = Client::with_no_tokens(server.uri()).unwrap();
+        let mut out = Vec::new();
+        let metadata = client.process_request_to_writer(&sample_request(), &mut out).await.unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(parsed, serde_json::json!({"a": [1, 2, 3], "b": "nested \"quote\""}));
+        assert_eq!(metadata.validation.input_hash, "abc123");
+    }
+
+    #[tokio::test]
+    async fn process_request_to_writer_errors_when_result_field_is_missing() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/process/request"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(
+                r#"{"validation":{"status":"ok","is_valid":true,"input_hash":"abc","errors":[]},"processor_used":"local","processing_time_ms":1.0}"#,
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+
+        let client = Client::with_no_tokens(server.uri()).unwrap();
+        let mut out = Vec::new();
+        let err = client.process_request_to_writer(&sample_request(), &mut out).await.unwrap_err();
+
+        assert!(err.to_string().contains("result"));
+    }
+
+    #[tokio::test]
+    async fn process_request_to_writer_honors_the_request_timeout() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/process/request"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_delay(Duration::from_millis(500))
+                    .set_body_raw(r#"{"result":"late","validation":{"status":"ok","is_valid":true,"input_hash":"x","errors":[]},"processor_used":"local","processing_time_ms":1.0}"#, "application/json"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::with_no_tokens(server.uri()).unwrap();
+        let mut request = sample_request();
+        request.timeout_seconds = Some(0.05);
+        let mut out = Vec::new();
+
+        let result = client.process_request_to_writer(&request, &mut out).await;
+
+        assert!(result.is_err(), "expected the slow response to time out, got {:?}", result);
+    }
+
+    fn sample_output_body() -> String {
+        r#"{"result":"ok","validation":{"status":"ok","is_valid":true,"input_hash":"deadbeef","errors":[]},"processor_used":"cloud","processing_time_ms":1.0}"#.to_string()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn process_request_retries_once_after_a_503_then_succeeds() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/process/request"))
+            .respond_with(wiremock::ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/process/request"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(sample_output_body(), "application/json"))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder(server.uri())
+            .max_retries(1)
+            .base_backoff(Duration::from_millis(10))
+            .build()
+            .unwrap();
+
+        let output = client.process_request(&sample_request()).await.unwrap();
+
+        assert_eq!(output.retries_attempted, 1);
+    }
+
+    #[tokio::test]
+    async fn process_request_never_retries_a_non_retryable_4xx() {
+        let server = wiremock::MockServer::start().await;
+        let mock = wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/process/request"))
+            .respond_with(wiremock::ResponseTemplate::new(400).set_body_raw("bad request", "text/plain"))
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+
+        let client = Client::builder(server.uri())
+            .max_retries(3)
+            .base_backoff(Duration::from_millis(10))
+            .build()
+            .unwrap();
+
+        let err = client.process_request(&sample_request()).await.unwrap_err();
+
+        assert!(err.to_string().contains("400"));
+        drop(mock); // asserts exactly one request was made
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn process_request_honors_retry_after_over_computed_backoff() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/process/request"))
+            .respond_with(wiremock::ResponseTemplate::new(503).insert_header("Retry-After", "2"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/process/request"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(sample_output_body(), "application/json"))
+            .mount(&server)
+            .await;
+
+        // base_backoff is far smaller than the 2s Retry-After: if the server's
+        // header weren't honored, this would resolve almost instantly.
+        let client = Client::builder(server.uri())
+            .max_retries(1)
+            .base_backoff(Duration::from_millis(1))
+            .build()
+            .unwrap();
+
+        let before = tokio::time::Instant::now();
+        let output = client.process_request(&sample_request()).await.unwrap();
+        let elapsed = before.elapsed();
+
+        assert_eq!(output.retries_attempted, 1);
+        assert!(elapsed >= Duration::from_secs(2), "elapsed {:?} did not honor Retry-After", elapsed);
+    }
+
+    #[tokio::test]
+    async fn wait_before_retry_errors_instead_of_sleeping_past_the_deadline() {
+        let client = Client::builder("http://example.invalid".to_string())
+            .max_retries(5)
+            .build()
+            .unwrap();
+
+        // A concrete (non-random) backoff, so the deadline comparison below
+        // is deterministic regardless of jittered_backoff's draw.
+        let backoff = Duration::from_secs(60);
+        let deadline = Instant::now() + Duration::from_millis(10);
+
+        let err = client.wait_before_retry(0, Some(backoff), Some(deadline)).await.unwrap_err();
+
+        assert!(err.to_string().contains("timed out"));
     }
 }